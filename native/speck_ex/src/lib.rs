@@ -1,12 +1,23 @@
+use cipher::block_padding::{Pkcs7, ZeroPadding};
 use cipher::generic_array::GenericArray;
-use cipher::{BlockDecrypt, BlockEncrypt, KeyInit, KeyIvInit, StreamCipher};
+use cipher::{
+    BlockDecrypt, BlockDecryptMut, BlockEncrypt, BlockEncryptMut, KeyInit, KeyIvInit, StreamCipher,
+};
 use poly1305::{universal_hash::UniversalHash, Poly1305};
-use rustler::{Binary, Env, Error, OwnedBinary, ResourceArc};
+use rustler::{Atom, Binary, Env, Error, OwnedBinary, ResourceArc};
+use std::sync::Mutex;
 use speck_cipher::{
     Speck128_128, Speck128_192, Speck128_256, Speck32_64, Speck48_72, Speck48_96, Speck64_128,
     Speck64_96, Speck96_144, Speck96_96,
 };
 
+mod atoms {
+    rustler::atoms! {
+        pkcs7,
+        zero,
+    }
+}
+
 // Resource wrapper
 struct SpeckCipher<T>(T);
 
@@ -176,6 +187,234 @@ impl_speck_ctr!(speck128_128_ctr_crypt, ctr::Ctr128BE<Speck128_128>);
 impl_speck_ctr!(speck128_192_ctr_crypt, ctr::Ctr128BE<Speck128_192>);
 impl_speck_ctr!(speck128_256_ctr_crypt, ctr::Ctr128BE<Speck128_256>);
 
+// CBC/ECB mode helper functions using the cbc/ecb crates. Padding is selected by the
+// caller via a mode atom (:pkcs7 or :zero) so fixed-width records can round-trip without
+// the extra full block PKCS#7 appends to data that is already a block multiple.
+fn cbc_encrypt_padded<'a, C, P>(
+    env: Env<'a>,
+    key: &[u8],
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Binary<'a>, Error>
+where
+    C: BlockEncryptMut + BlockDecryptMut + KeyInit,
+    P: cipher::block_padding::Padding<C::BlockSize>,
+{
+    let enc = cbc::Encryptor::<C>::new_from_slices(key, iv).map_err(|_| Error::BadArg)?;
+    let ct = enc.encrypt_padded_vec_mut::<P>(data);
+
+    let mut owned = OwnedBinary::new(ct.len()).ok_or(Error::Atom("allocation_failed"))?;
+    owned.as_mut_slice().copy_from_slice(&ct);
+    Ok(owned.release(env))
+}
+
+fn cbc_decrypt_padded<'a, C, P>(
+    env: Env<'a>,
+    key: &[u8],
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Binary<'a>, Error>
+where
+    C: BlockEncryptMut + BlockDecryptMut + KeyInit,
+    P: cipher::block_padding::Padding<C::BlockSize>,
+{
+    let dec = cbc::Decryptor::<C>::new_from_slices(key, iv).map_err(|_| Error::BadArg)?;
+    let pt = dec
+        .decrypt_padded_vec_mut::<P>(data)
+        .map_err(|_| Error::Atom("unpad_failed"))?;
+
+    let mut owned = OwnedBinary::new(pt.len()).ok_or(Error::Atom("allocation_failed"))?;
+    owned.as_mut_slice().copy_from_slice(&pt);
+    Ok(owned.release(env))
+}
+
+fn ecb_encrypt_padded<'a, C, P>(env: Env<'a>, key: &[u8], data: &[u8]) -> Result<Binary<'a>, Error>
+where
+    C: BlockEncryptMut + BlockDecryptMut + KeyInit,
+    P: cipher::block_padding::Padding<C::BlockSize>,
+{
+    let enc = ecb::Encryptor::<C>::new_from_slice(key).map_err(|_| Error::BadArg)?;
+    let ct = enc.encrypt_padded_vec_mut::<P>(data);
+
+    let mut owned = OwnedBinary::new(ct.len()).ok_or(Error::Atom("allocation_failed"))?;
+    owned.as_mut_slice().copy_from_slice(&ct);
+    Ok(owned.release(env))
+}
+
+fn ecb_decrypt_padded<'a, C, P>(env: Env<'a>, key: &[u8], data: &[u8]) -> Result<Binary<'a>, Error>
+where
+    C: BlockEncryptMut + BlockDecryptMut + KeyInit,
+    P: cipher::block_padding::Padding<C::BlockSize>,
+{
+    let dec = ecb::Decryptor::<C>::new_from_slice(key).map_err(|_| Error::BadArg)?;
+    let pt = dec
+        .decrypt_padded_vec_mut::<P>(data)
+        .map_err(|_| Error::Atom("unpad_failed"))?;
+
+    let mut owned = OwnedBinary::new(pt.len()).ok_or(Error::Atom("allocation_failed"))?;
+    owned.as_mut_slice().copy_from_slice(&pt);
+    Ok(owned.release(env))
+}
+
+// Macro to generate CBC mode NIFs for a given block size, with selectable padding
+macro_rules! impl_speck_cbc {
+    ($name_encrypt:ident, $name_decrypt:ident, $cipher_type:ty) => {
+        #[rustler::nif]
+        fn $name_encrypt<'a>(
+            env: Env<'a>,
+            key: Binary,
+            iv: Binary,
+            data: Binary,
+            padding: Atom,
+        ) -> Result<Binary<'a>, Error> {
+            if padding == atoms::zero() {
+                cbc_encrypt_padded::<$cipher_type, ZeroPadding>(
+                    env,
+                    key.as_slice(),
+                    iv.as_slice(),
+                    data.as_slice(),
+                )
+            } else if padding == atoms::pkcs7() {
+                cbc_encrypt_padded::<$cipher_type, Pkcs7>(
+                    env,
+                    key.as_slice(),
+                    iv.as_slice(),
+                    data.as_slice(),
+                )
+            } else {
+                Err(Error::BadArg)
+            }
+        }
+
+        #[rustler::nif]
+        fn $name_decrypt<'a>(
+            env: Env<'a>,
+            key: Binary,
+            iv: Binary,
+            data: Binary,
+            padding: Atom,
+        ) -> Result<Binary<'a>, Error> {
+            if padding == atoms::zero() {
+                cbc_decrypt_padded::<$cipher_type, ZeroPadding>(
+                    env,
+                    key.as_slice(),
+                    iv.as_slice(),
+                    data.as_slice(),
+                )
+            } else if padding == atoms::pkcs7() {
+                cbc_decrypt_padded::<$cipher_type, Pkcs7>(
+                    env,
+                    key.as_slice(),
+                    iv.as_slice(),
+                    data.as_slice(),
+                )
+            } else {
+                Err(Error::BadArg)
+            }
+        }
+    };
+}
+
+// Macro to generate ECB mode NIFs for a given block size, with selectable padding
+macro_rules! impl_speck_ecb {
+    ($name_encrypt:ident, $name_decrypt:ident, $cipher_type:ty) => {
+        #[rustler::nif]
+        fn $name_encrypt<'a>(
+            env: Env<'a>,
+            key: Binary,
+            data: Binary,
+            padding: Atom,
+        ) -> Result<Binary<'a>, Error> {
+            if padding == atoms::zero() {
+                ecb_encrypt_padded::<$cipher_type, ZeroPadding>(env, key.as_slice(), data.as_slice())
+            } else if padding == atoms::pkcs7() {
+                ecb_encrypt_padded::<$cipher_type, Pkcs7>(env, key.as_slice(), data.as_slice())
+            } else {
+                Err(Error::BadArg)
+            }
+        }
+
+        #[rustler::nif]
+        fn $name_decrypt<'a>(
+            env: Env<'a>,
+            key: Binary,
+            data: Binary,
+            padding: Atom,
+        ) -> Result<Binary<'a>, Error> {
+            if padding == atoms::zero() {
+                ecb_decrypt_padded::<$cipher_type, ZeroPadding>(env, key.as_slice(), data.as_slice())
+            } else if padding == atoms::pkcs7() {
+                ecb_decrypt_padded::<$cipher_type, Pkcs7>(env, key.as_slice(), data.as_slice())
+            } else {
+                Err(Error::BadArg)
+            }
+        }
+    };
+}
+
+// Generate CBC/ECB NIFs for every block size (32...128-bit)
+impl_speck_cbc!(speck32_64_cbc_encrypt, speck32_64_cbc_decrypt, Speck32_64);
+impl_speck_cbc!(speck48_72_cbc_encrypt, speck48_72_cbc_decrypt, Speck48_72);
+impl_speck_cbc!(speck48_96_cbc_encrypt, speck48_96_cbc_decrypt, Speck48_96);
+impl_speck_cbc!(speck64_96_cbc_encrypt, speck64_96_cbc_decrypt, Speck64_96);
+impl_speck_cbc!(
+    speck64_128_cbc_encrypt,
+    speck64_128_cbc_decrypt,
+    Speck64_128
+);
+impl_speck_cbc!(speck96_96_cbc_encrypt, speck96_96_cbc_decrypt, Speck96_96);
+impl_speck_cbc!(
+    speck96_144_cbc_encrypt,
+    speck96_144_cbc_decrypt,
+    Speck96_144
+);
+impl_speck_cbc!(
+    speck128_128_cbc_encrypt,
+    speck128_128_cbc_decrypt,
+    Speck128_128
+);
+impl_speck_cbc!(
+    speck128_192_cbc_encrypt,
+    speck128_192_cbc_decrypt,
+    Speck128_192
+);
+impl_speck_cbc!(
+    speck128_256_cbc_encrypt,
+    speck128_256_cbc_decrypt,
+    Speck128_256
+);
+
+impl_speck_ecb!(speck32_64_ecb_encrypt, speck32_64_ecb_decrypt, Speck32_64);
+impl_speck_ecb!(speck48_72_ecb_encrypt, speck48_72_ecb_decrypt, Speck48_72);
+impl_speck_ecb!(speck48_96_ecb_encrypt, speck48_96_ecb_decrypt, Speck48_96);
+impl_speck_ecb!(speck64_96_ecb_encrypt, speck64_96_ecb_decrypt, Speck64_96);
+impl_speck_ecb!(
+    speck64_128_ecb_encrypt,
+    speck64_128_ecb_decrypt,
+    Speck64_128
+);
+impl_speck_ecb!(speck96_96_ecb_encrypt, speck96_96_ecb_decrypt, Speck96_96);
+impl_speck_ecb!(
+    speck96_144_ecb_encrypt,
+    speck96_144_ecb_decrypt,
+    Speck96_144
+);
+impl_speck_ecb!(
+    speck128_128_ecb_encrypt,
+    speck128_128_ecb_decrypt,
+    Speck128_128
+);
+impl_speck_ecb!(
+    speck128_192_ecb_encrypt,
+    speck128_192_ecb_decrypt,
+    Speck128_192
+);
+impl_speck_ecb!(
+    speck128_256_ecb_encrypt,
+    speck128_256_ecb_decrypt,
+    Speck128_256
+);
+
 // Poly1305 AEAD helper functions
 fn compute_poly1305_tag(poly_key: &[u8], aad: &[u8], ciphertext: &[u8]) -> Result<[u8; 16], Error> {
     // Initialize Poly1305 with derived key
@@ -341,6 +580,554 @@ impl_speck_poly1305!(
     ctr::Ctr128BE<Speck128_256>
 );
 
+// CMAC (OMAC1) helper functions, used directly as a MAC and as the S2V core of SIV mode
+fn xor_in_place(a: &mut [u8], b: &[u8]) {
+    for (x, y) in a.iter_mut().zip(b) {
+        *x ^= y;
+    }
+}
+
+// Left-shift a block by one bit, XORing in the block-size-specific Rb constant whenever
+// the high bit that fell off was set. Rb is the byte representation of the lowest-degree
+// irreducible polynomial for the block size, per NIST SP 800-38B. SP 800-38B only defines
+// Rb for 64-bit and 128-bit blocks, so CMAC/SIV are restricted to the Speck variants with
+// one of those two block sizes (see the NIF generation lists below) rather than guessing
+// a constant for the 32/48/96-bit blocks.
+fn cmac_dbl(block: &mut [u8]) {
+    let msb_set = block[0] & 0x80 != 0;
+    let mut carry = 0u8;
+    for byte in block.iter_mut().rev() {
+        let next_carry = *byte >> 7;
+        *byte = (*byte << 1) | carry;
+        carry = next_carry;
+    }
+    if msb_set {
+        let rb: u8 = match block.len() {
+            8 => 0x1b,
+            16 => 0x87,
+            n => panic!("unsupported CMAC block size: {n}"),
+        };
+        let last = block.len() - 1;
+        block[last] ^= rb;
+    }
+}
+
+fn cmac_subkeys<T: BlockEncrypt>(cipher: &T) -> (Vec<u8>, Vec<u8>) {
+    let mut l = GenericArray::<u8, T::BlockSize>::default();
+    cipher.encrypt_block(&mut l);
+
+    let mut k1 = l.to_vec();
+    cmac_dbl(&mut k1);
+    let mut k2 = k1.clone();
+    cmac_dbl(&mut k2);
+    (k1, k2)
+}
+
+// Plain CMAC/OMAC1 over an arbitrary-length message, per SP 800-38B.
+fn cmac_raw<T: BlockEncrypt>(cipher: &T, msg: &[u8]) -> Vec<u8> {
+    let block_size = GenericArray::<u8, T::BlockSize>::default().len();
+    let (k1, k2) = cmac_subkeys(cipher);
+
+    let num_blocks = if msg.is_empty() {
+        1
+    } else {
+        msg.len().div_ceil(block_size)
+    };
+    let final_is_complete = !msg.is_empty() && msg.len() % block_size == 0;
+
+    let mut state = vec![0u8; block_size];
+    for i in 0..num_blocks {
+        let start = i * block_size;
+        let mut block = if i < num_blocks - 1 {
+            msg[start..start + block_size].to_vec()
+        } else if final_is_complete {
+            let mut b = msg[start..].to_vec();
+            xor_in_place(&mut b, &k1);
+            b
+        } else {
+            let chunk = &msg[start..];
+            let mut b = vec![0u8; block_size];
+            b[..chunk.len()].copy_from_slice(chunk);
+            b[chunk.len()] = 0x80;
+            xor_in_place(&mut b, &k2);
+            b
+        };
+        xor_in_place(&mut block, &state);
+
+        let mut ga = GenericArray::<u8, T::BlockSize>::clone_from_slice(&block);
+        cipher.encrypt_block(&mut ga);
+        state = ga.to_vec();
+    }
+    state
+}
+
+// S2V from RFC 5297: folds a sequence of associated-data strings and the plaintext into a
+// single synthetic IV, using CMAC as the underlying PRF.
+fn s2v<T: BlockEncrypt>(cipher: &T, ad: &[&[u8]], plaintext: &[u8]) -> Vec<u8> {
+    let block_size = GenericArray::<u8, T::BlockSize>::default().len();
+    let mut d = cmac_raw(cipher, &vec![0u8; block_size]);
+
+    for s in ad {
+        cmac_dbl(&mut d);
+        let c = cmac_raw(cipher, s);
+        xor_in_place(&mut d, &c);
+    }
+
+    if plaintext.len() >= block_size {
+        let mut t = plaintext.to_vec();
+        let tail_start = t.len() - block_size;
+        xor_in_place(&mut t[tail_start..], &d);
+        cmac_raw(cipher, &t)
+    } else {
+        cmac_dbl(&mut d);
+        let mut padded = plaintext.to_vec();
+        padded.push(0x80);
+        padded.resize(block_size, 0);
+        xor_in_place(&mut d, &padded);
+        cmac_raw(cipher, &d)
+    }
+}
+
+// Clears the top bit of each of the last two counter words of the synthetic IV before it is
+// used as a CTR nonce, per RFC 5297 §2.4, scaled from 32-bit words to this block size.
+fn clear_siv_counter_bits(v: &mut [u8]) {
+    let half = v.len() / 2;
+    let quarter = half / 2;
+    v[half] &= 0x7f;
+    v[half + quarter] &= 0x7f;
+}
+
+// Macro to generate CMAC NIFs for a given block size
+macro_rules! impl_speck_cmac {
+    ($name_compute:ident, $cipher_type:ty) => {
+        #[rustler::nif]
+        fn $name_compute<'a>(env: Env<'a>, key: Binary, msg: Binary) -> Result<Binary<'a>, Error> {
+            let cipher = init::<$cipher_type>(key.as_slice())?;
+            let tag = cmac_raw(&cipher.0, msg.as_slice());
+
+            let mut owned = OwnedBinary::new(tag.len()).ok_or(Error::Atom("allocation_failed"))?;
+            owned.as_mut_slice().copy_from_slice(&tag);
+            Ok(owned.release(env))
+        }
+    };
+}
+
+// Generate CMAC NIFs - only for the 64-bit and 128-bit block variants, since SP 800-38B
+// only defines an Rb constant for those two block sizes (see `cmac_dbl`)
+impl_speck_cmac!(speck64_96_cmac, Speck64_96);
+impl_speck_cmac!(speck64_128_cmac, Speck64_128);
+impl_speck_cmac!(speck128_128_cmac, Speck128_128);
+impl_speck_cmac!(speck128_192_cmac, Speck128_192);
+impl_speck_cmac!(speck128_256_cmac, Speck128_256);
+
+// SIV helper functions: deterministic nonce-misuse-resistant AEAD built from S2V (CMAC) +
+// CTR, per RFC 5297. The input key is split into two equal halves, K1 (S2V) and K2 (CTR).
+fn speck_siv_encrypt_impl<'a, C, Ctr>(
+    env: Env<'a>,
+    key: &[u8],
+    ad: &[&[u8]],
+    plaintext: &[u8],
+) -> Result<(Binary<'a>, Binary<'a>), Error>
+where
+    C: BlockEncrypt + KeyInit,
+    Ctr: KeyIvInit + StreamCipher,
+{
+    let key_size = GenericArray::<u8, C::KeySize>::default().len();
+    if key.len() != 2 * key_size {
+        return Err(Error::BadArg);
+    }
+    let (k1, k2) = key.split_at(key_size);
+    let mac_cipher = init::<C>(k1)?;
+
+    let v = s2v(&mac_cipher.0, ad, plaintext);
+    let mut nonce = v.clone();
+    clear_siv_counter_bits(&mut nonce);
+
+    let mut ctr = Ctr::new(GenericArray::from_slice(k2), GenericArray::from_slice(&nonce));
+    let mut ciphertext_owned =
+        OwnedBinary::new(plaintext.len()).ok_or(Error::Atom("allocation_failed"))?;
+    ciphertext_owned.as_mut_slice().copy_from_slice(plaintext);
+    ctr.apply_keystream(ciphertext_owned.as_mut_slice());
+
+    let mut tag_owned = OwnedBinary::new(v.len()).ok_or(Error::Atom("allocation_failed"))?;
+    tag_owned.as_mut_slice().copy_from_slice(&v);
+
+    Ok((tag_owned.release(env), ciphertext_owned.release(env)))
+}
+
+fn speck_siv_decrypt_impl<'a, C, Ctr>(
+    env: Env<'a>,
+    key: &[u8],
+    ad: &[&[u8]],
+    tag: &[u8],
+    ciphertext: &[u8],
+) -> Result<Binary<'a>, Error>
+where
+    C: BlockEncrypt + KeyInit,
+    Ctr: KeyIvInit + StreamCipher,
+{
+    let key_size = GenericArray::<u8, C::KeySize>::default().len();
+    if key.len() != 2 * key_size {
+        return Err(Error::BadArg);
+    }
+    // `tag` is attacker-controlled input being verified, not yet-trusted data - it must be
+    // validated before it's used to build the CTR nonce below (GenericArray::from_slice
+    // asserts on length instead of returning a Result, which would otherwise panic across
+    // the NIF boundary on a malformed tag).
+    let block_size = GenericArray::<u8, C::BlockSize>::default().len();
+    if tag.len() != block_size {
+        return Err(Error::BadArg);
+    }
+    let (k1, k2) = key.split_at(key_size);
+    let mac_cipher = init::<C>(k1)?;
+
+    let mut nonce = tag.to_vec();
+    clear_siv_counter_bits(&mut nonce);
+
+    let mut ctr = Ctr::new(GenericArray::from_slice(k2), GenericArray::from_slice(&nonce));
+    let mut plaintext_owned =
+        OwnedBinary::new(ciphertext.len()).ok_or(Error::Atom("allocation_failed"))?;
+    plaintext_owned.as_mut_slice().copy_from_slice(ciphertext);
+    ctr.apply_keystream(plaintext_owned.as_mut_slice());
+
+    let expected_v = s2v(&mac_cipher.0, ad, plaintext_owned.as_slice());
+
+    use subtle::ConstantTimeEq;
+    if !bool::from(expected_v.as_slice().ct_eq(tag)) {
+        return Err(Error::Atom("authentication_failed"));
+    }
+
+    Ok(plaintext_owned.release(env))
+}
+
+// Macro to generate SIV NIFs for a given (CTR-compatible, 64- or 128-bit block) variant
+macro_rules! impl_speck_siv {
+    ($name_encrypt:ident, $name_decrypt:ident, $cipher_type:ty, $ctr_type:ty) => {
+        #[rustler::nif]
+        fn $name_encrypt<'a>(
+            env: Env<'a>,
+            key: Binary,
+            ad: Binary,
+            plaintext: Binary,
+        ) -> Result<(Binary<'a>, Binary<'a>), Error> {
+            speck_siv_encrypt_impl::<$cipher_type, $ctr_type>(
+                env,
+                key.as_slice(),
+                &[ad.as_slice()],
+                plaintext.as_slice(),
+            )
+        }
+
+        #[rustler::nif]
+        fn $name_decrypt<'a>(
+            env: Env<'a>,
+            key: Binary,
+            ad: Binary,
+            tag: Binary,
+            ciphertext: Binary,
+        ) -> Result<Binary<'a>, Error> {
+            speck_siv_decrypt_impl::<$cipher_type, $ctr_type>(
+                env,
+                key.as_slice(),
+                &[ad.as_slice()],
+                tag.as_slice(),
+                ciphertext.as_slice(),
+            )
+        }
+    };
+}
+
+// Generate SIV NIFs - only for the 64-bit and 128-bit block variants, for the same Rb
+// reason CMAC is restricted above (the 32-bit speck32_64 block has no defined Rb constant)
+impl_speck_siv!(
+    speck64_96_siv_encrypt,
+    speck64_96_siv_decrypt,
+    Speck64_96,
+    ctr::Ctr64BE<Speck64_96>
+);
+impl_speck_siv!(
+    speck64_128_siv_encrypt,
+    speck64_128_siv_decrypt,
+    Speck64_128,
+    ctr::Ctr64BE<Speck64_128>
+);
+impl_speck_siv!(
+    speck128_128_siv_encrypt,
+    speck128_128_siv_decrypt,
+    Speck128_128,
+    ctr::Ctr128BE<Speck128_128>
+);
+impl_speck_siv!(
+    speck128_192_siv_encrypt,
+    speck128_192_siv_decrypt,
+    Speck128_192,
+    ctr::Ctr128BE<Speck128_192>
+);
+impl_speck_siv!(
+    speck128_256_siv_encrypt,
+    speck128_256_siv_decrypt,
+    Speck128_256,
+    ctr::Ctr128BE<Speck128_256>
+);
+
+// Password-based key derivation NIFs, so passwords can be turned into correctly-sized
+// Speck keys without leaving the NIF boundary. `dklen` lets callers derive exactly the
+// 8/9/12/16/18/24/32-byte keys the various Speck variants need.
+#[rustler::nif]
+fn pbkdf2_hmac_sha256<'a>(
+    env: Env<'a>,
+    password: Binary,
+    salt: Binary,
+    iterations: u32,
+    dklen: usize,
+) -> Result<Binary<'a>, Error> {
+    let mut owned = OwnedBinary::new(dklen).ok_or(Error::Atom("allocation_failed"))?;
+    pbkdf2::pbkdf2_hmac::<sha2::Sha256>(
+        password.as_slice(),
+        salt.as_slice(),
+        iterations,
+        owned.as_mut_slice(),
+    );
+    Ok(owned.release(env))
+}
+
+#[rustler::nif]
+fn scrypt<'a>(
+    env: Env<'a>,
+    password: Binary,
+    salt: Binary,
+    log_n: u8,
+    r: u32,
+    p: u32,
+    dklen: usize,
+) -> Result<Binary<'a>, Error> {
+    let params = scrypt::Params::new(log_n, r, p, dklen).map_err(|_| Error::BadArg)?;
+
+    let mut owned = OwnedBinary::new(dklen).ok_or(Error::Atom("allocation_failed"))?;
+    scrypt::scrypt(
+        password.as_slice(),
+        salt.as_slice(),
+        &params,
+        owned.as_mut_slice(),
+    )
+    .map_err(|_| Error::Atom("kdf_failed"))?;
+    Ok(owned.release(env))
+}
+
+#[rustler::nif]
+fn argon2id<'a>(
+    env: Env<'a>,
+    password: Binary,
+    salt: Binary,
+    t_cost: u32,
+    m_cost: u32,
+    parallelism: u32,
+    dklen: usize,
+) -> Result<Binary<'a>, Error> {
+    let params = argon2::Params::new(m_cost, t_cost, parallelism, Some(dklen))
+        .map_err(|_| Error::BadArg)?;
+    let argon2 = argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+
+    let mut owned = OwnedBinary::new(dklen).ok_or(Error::Atom("allocation_failed"))?;
+    argon2
+        .hash_password_into(password.as_slice(), salt.as_slice(), owned.as_mut_slice())
+        .map_err(|_| Error::Atom("kdf_failed"))?;
+    Ok(owned.release(env))
+}
+
+// Streaming AEAD resource. Holds the key, a caller-supplied base nonce and a monotonically
+// increasing sequence counter, so chunks can be sealed/opened in order with a guaranteed
+// unique per-chunk nonce, modeled on the OHTTP AEAD construction.
+//
+// For Ctr{32,64,128}BE the counter *is* the whole IV, with no fixed region of its own, so
+// naively XORing `seq` into the low-order bytes (the bytes CTR walks while counting) lets
+// one chunk's keystream run straight into the next chunk's starting counter value - unique
+// IVs do not imply disjoint keystreams when the IV is the counter. Instead, `apply_seq_xor`
+// reserves the low `COUNTER_RESERVE_BYTES` of the nonce for CTR's own counting and XORs
+// `seq` only into the bytes above that reserve, so each chunk gets a distinct, disjoint
+// counter range as long as it stays within 2^(8 * `COUNTER_RESERVE_BYTES`) blocks -
+// comfortably enough for the Poly1305-key blocks plus any realistic chunk size.
+struct StreamState {
+    key: Vec<u8>,
+    base_nonce: Vec<u8>,
+    seq: Mutex<u64>,
+}
+
+const COUNTER_RESERVE_BYTES: usize = 4;
+
+fn stream_init<Ctr: KeyIvInit>(
+    key: &[u8],
+    base_nonce: &[u8],
+) -> Result<ResourceArc<StreamState>, Error> {
+    let key_size = GenericArray::<u8, Ctr::KeySize>::default().len();
+    let nonce_size = GenericArray::<u8, Ctr::IvSize>::default().len();
+    if key.len() != key_size || base_nonce.len() != nonce_size {
+        return Err(Error::BadArg);
+    }
+    Ok(ResourceArc::new(StreamState {
+        key: key.to_vec(),
+        base_nonce: base_nonce.to_vec(),
+        seq: Mutex::new(0),
+    }))
+}
+
+// `seq` is carried in the bytes above the counter reserve, so it wraps (and must stop)
+// once it no longer fits there rather than at `u64::MAX`.
+fn seq_limit(nonce_len: usize) -> u64 {
+    let seq_region = nonce_len - COUNTER_RESERVE_BYTES.min(nonce_len);
+    if seq_region >= 8 {
+        u64::MAX
+    } else {
+        1u64 << (8 * seq_region)
+    }
+}
+
+fn apply_seq_xor(base_nonce: &[u8], seq: u64) -> Vec<u8> {
+    let mut nonce = base_nonce.to_vec();
+    let len = nonce.len();
+    let reserve = COUNTER_RESERVE_BYTES.min(len);
+
+    // Always start each chunk's counter from the same origin so only the seq-carrying
+    // bytes below distinguish chunks from one another.
+    for b in &mut nonce[len - reserve..] {
+        *b = 0;
+    }
+
+    let seq_region = len - reserve;
+    let seq_bytes = seq.to_be_bytes();
+    let n = seq_region.min(seq_bytes.len());
+    let offset = seq_region - n;
+    for i in 0..n {
+        nonce[offset + i] ^= seq_bytes[seq_bytes.len() - n + i];
+    }
+    nonce
+}
+
+// Macro to generate streaming AEAD NIFs for a CTR-compatible variant
+macro_rules! impl_speck_stream {
+    ($name_init:ident, $name_seal:ident, $name_open:ident, $ctr_type:ty) => {
+        #[rustler::nif]
+        fn $name_init(key: Binary, base_nonce: Binary) -> Result<ResourceArc<StreamState>, Error> {
+            stream_init::<$ctr_type>(key.as_slice(), base_nonce.as_slice())
+        }
+
+        #[rustler::nif]
+        fn $name_seal<'a>(
+            env: Env<'a>,
+            state: ResourceArc<StreamState>,
+            aad: Binary,
+            plaintext: Binary,
+        ) -> Result<(Binary<'a>, Binary<'a>), Error> {
+            // Held for the whole call so a concurrent seal/open on the same resource can't
+            // observe and consume the same sequence number.
+            let mut seq = state.seq.lock().map_err(|_| Error::Atom("lock_poisoned"))?;
+            if *seq >= seq_limit(state.base_nonce.len()) {
+                return Err(Error::Atom("counter_overflow"));
+            }
+            let nonce = apply_seq_xor(&state.base_nonce, *seq);
+            let result = speck_poly1305_encrypt_impl::<$ctr_type>(
+                env,
+                &state.key,
+                &nonce,
+                plaintext.as_slice(),
+                aad.as_slice(),
+            )?;
+            *seq += 1;
+            Ok(result)
+        }
+
+        #[rustler::nif]
+        fn $name_open<'a>(
+            env: Env<'a>,
+            state: ResourceArc<StreamState>,
+            aad: Binary,
+            ciphertext: Binary,
+            tag: Binary,
+        ) -> Result<Binary<'a>, Error> {
+            // Held across the decrypt so two concurrent opens on the same resource can't
+            // both read the same sequence number before either advances it, and so a
+            // chunk only ever consumes its sequence number once it has authenticated -
+            // a forged/corrupted chunk can be retried instead of desyncing the stream.
+            let mut seq = state.seq.lock().map_err(|_| Error::Atom("lock_poisoned"))?;
+            if *seq >= seq_limit(state.base_nonce.len()) {
+                return Err(Error::Atom("counter_overflow"));
+            }
+            let nonce = apply_seq_xor(&state.base_nonce, *seq);
+            let plaintext = speck_poly1305_decrypt_impl::<$ctr_type>(
+                env,
+                &state.key,
+                &nonce,
+                ciphertext.as_slice(),
+                tag.as_slice(),
+                aad.as_slice(),
+            )?;
+            *seq += 1;
+            Ok(plaintext)
+        }
+    };
+}
+
+// Generate streaming AEAD NIFs - only for the 64-bit and 128-bit block variants. The
+// 32-bit speck32_64 nonce is exactly `COUNTER_RESERVE_BYTES` wide, leaving no room to
+// carry `seq` at all, which would collapse every chunk onto the same nonce.
+impl_speck_stream!(
+    speck64_96_stream_init,
+    speck64_96_stream_seal,
+    speck64_96_stream_open,
+    ctr::Ctr64BE<Speck64_96>
+);
+impl_speck_stream!(
+    speck64_128_stream_init,
+    speck64_128_stream_seal,
+    speck64_128_stream_open,
+    ctr::Ctr64BE<Speck64_128>
+);
+impl_speck_stream!(
+    speck128_128_stream_init,
+    speck128_128_stream_seal,
+    speck128_128_stream_open,
+    ctr::Ctr128BE<Speck128_128>
+);
+impl_speck_stream!(
+    speck128_192_stream_init,
+    speck128_192_stream_seal,
+    speck128_192_stream_open,
+    ctr::Ctr128BE<Speck128_192>
+);
+impl_speck_stream!(
+    speck128_256_stream_init,
+    speck128_256_stream_seal,
+    speck128_256_stream_open,
+    ctr::Ctr128BE<Speck128_256>
+);
+
+// HKDF (RFC 5869) NIFs, so a single master secret can deterministically derive independent
+// Speck keys, CTR base nonces and Poly1305 domains from context `info` labels, the same
+// extract-then-expand split hybrid encryption stacks like HPKE rely on.
+#[rustler::nif]
+fn hkdf_sha256_extract<'a>(env: Env<'a>, salt: Binary, ikm: Binary) -> Result<Binary<'a>, Error> {
+    let (prk, _) = hkdf::Hkdf::<sha2::Sha256>::extract(Some(salt.as_slice()), ikm.as_slice());
+
+    let mut owned = OwnedBinary::new(prk.len()).ok_or(Error::Atom("allocation_failed"))?;
+    owned.as_mut_slice().copy_from_slice(&prk);
+    Ok(owned.release(env))
+}
+
+#[rustler::nif]
+fn hkdf_sha256_expand<'a>(
+    env: Env<'a>,
+    prk: Binary,
+    info: Binary,
+    length: usize,
+) -> Result<Binary<'a>, Error> {
+    let hk = hkdf::Hkdf::<sha2::Sha256>::from_prk(prk.as_slice()).map_err(|_| Error::BadArg)?;
+
+    let mut owned = OwnedBinary::new(length).ok_or(Error::Atom("allocation_failed"))?;
+    hk.expand(info.as_slice(), owned.as_mut_slice())
+        .map_err(|_| Error::Atom("invalid_length"))?;
+    Ok(owned.release(env))
+}
+
 #[allow(non_local_definitions)]
 fn on_load(env: Env, _info: rustler::Term) -> bool {
     let _ = rustler::resource!(SpeckCipher<Speck32_64>, env);
@@ -353,6 +1140,7 @@ fn on_load(env: Env, _info: rustler::Term) -> bool {
     let _ = rustler::resource!(SpeckCipher<Speck128_128>, env);
     let _ = rustler::resource!(SpeckCipher<Speck128_192>, env);
     let _ = rustler::resource!(SpeckCipher<Speck128_256>, env);
+    let _ = rustler::resource!(StreamState, env);
     true
 }
 